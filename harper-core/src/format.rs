@@ -0,0 +1,329 @@
+use crate::{Span, Token, TokenKind, TokenStringExt};
+
+/// A single print instruction in an Oppen-style pretty-printer, specialized
+/// to the handful of spacing decisions this crate needs to make.
+#[derive(Debug, Clone)]
+enum Instruction<'a> {
+    /// Literal, non-whitespace content to emit verbatim, tagged with the
+    /// [`TokenKind`] it came from so `Break`s can make kind-aware spacing
+    /// decisions (e.g. around quotes and ellipses).
+    Text(&'a [char], TokenKind),
+    /// A point where the printer must decide whether to emit a single space
+    /// or nothing, based on the kinds flanking it. Backed by an existing
+    /// whitespace token in the source.
+    Break,
+    /// Like [`Break`], but synthesized where *no* whitespace token exists
+    /// in the source at all -- e.g. a sentence terminator directly
+    /// followed by the next sentence with nothing between them. Carries
+    /// the char offset the gap sits at, since there's no token to anchor
+    /// an edit to.
+    SynthesizedBreak(usize),
+    /// Start a group representing a sentence.
+    Begin,
+    /// End the innermost open sentence group.
+    End,
+}
+
+/// Lower a token stream into a sequence of print instructions, dropping the
+/// original whitespace tokens entirely -- they're replaced by [`Break`]s
+/// that the linear scan resolves.
+///
+/// A [`Instruction::SynthesizedBreak`] is inserted between two sentences
+/// that have no whitespace token between them at all, so "exactly one
+/// space follows a sentence terminator" holds even when the source has
+/// zero spaces there to begin with.
+fn lower<'a>(tokens: &[Token], source: &'a [char]) -> Vec<Instruction<'a>> {
+    let mut instructions = Vec::new();
+    let sentences: Vec<&[Token]> = tokens.iter_sentences().collect();
+
+    for (i, sentence) in sentences.iter().enumerate() {
+        if i > 0 {
+            let prev_ends_with_terminator = sentences[i - 1]
+                .last()
+                .is_some_and(|t| t.kind.is_sentence_terminator());
+            let starts_with_space = sentence.first().is_some_and(|t| t.kind.is_whitespace());
+
+            if prev_ends_with_terminator && !starts_with_space {
+                let offset = sentence.first().map(|t| t.span.start).unwrap_or(0);
+                instructions.push(Instruction::SynthesizedBreak(offset));
+            }
+        }
+
+        instructions.push(Instruction::Begin);
+
+        for token in *sentence {
+            if token.kind.is_space() {
+                instructions.push(Instruction::Break);
+            } else {
+                instructions.push(Instruction::Text(token.span.get_content(source), token.kind));
+            }
+        }
+
+        instructions.push(Instruction::End);
+    }
+
+    instructions
+}
+
+/// Tracks, across a single sentence, whether the *next* quote token
+/// encountered should be treated as an opening or a closing quote.
+///
+/// Harper's tokenizer doesn't distinguish opening/closing quotes itself, so
+/// this assumes the common case of alternating, well-paired quotes: the
+/// first quote in a sentence opens, the second closes, and so on. Parity is
+/// reset at each sentence boundary, so a stray or mis-tokenized quote can
+/// only throw off spacing within its own sentence, not the rest of the
+/// document.
+#[derive(Debug, Default)]
+struct QuoteParity {
+    seen: usize,
+}
+
+impl QuoteParity {
+    /// Whether the `n`-th (0-indexed) quote token is an opener.
+    fn is_opening(&self, index: usize) -> bool {
+        index % 2 == 0
+    }
+
+    /// Whether the quote token about to be consumed would open a pair.
+    fn next_is_opening(&self) -> bool {
+        self.is_opening(self.seen)
+    }
+
+    /// Record that a quote token was consumed, returning whether it opened
+    /// a pair.
+    fn consume(&mut self) -> bool {
+        let opening = self.next_is_opening();
+        self.seen += 1;
+        opening
+    }
+}
+
+/// Decide, for the token kinds immediately before and after a break,
+/// whether it should be rendered as a single space or collapsed away
+/// entirely.
+fn break_wants_space(
+    before: Option<(&[char], TokenKind)>,
+    before_was_opening_quote: bool,
+    after: Option<(&[char], TokenKind)>,
+    after_is_opening_quote: bool,
+) -> bool {
+    let Some((after_content, after_kind)) = after else {
+        return false;
+    };
+
+    // No space before closing punctuation, e.g. `word .` -> `word.`.
+    if matches!(after_content.first(), Some('.' | ',' | ';' | ':' | '!' | '?')) {
+        return false;
+    }
+
+    // No space before an ellipsis, e.g. `word ...` -> `word...`.
+    if after_kind.is_ellipsis() {
+        return false;
+    }
+
+    // No space right before a closing quote: `"word "` -> `"word"`.
+    if after_kind.is_quote() && !after_is_opening_quote {
+        return false;
+    }
+
+    // No space right after an opening quote: `" word"` -> `"word"`.
+    if before.is_some_and(|(_, kind)| kind.is_quote()) && before_was_opening_quote {
+        return false;
+    }
+
+    before.is_some()
+}
+
+/// Normalize the spacing of a token stream: runs of spaces collapse to one,
+/// spaces before `.,;:!?` are removed, exactly one space follows a sentence
+/// terminator (synthesizing one if the source has none at all), and
+/// ellipsis/quote spacing is normalized (no space before an ellipsis, and
+/// no space just inside a pair of quotes).
+///
+/// Returns the normalized text alongside the list of `(Span, replacement)`
+/// edits that would turn `source` into it, so callers can surface the
+/// result as ordinary grammar-style fixes instead of rewriting the whole
+/// buffer.
+pub fn normalize_spacing(tokens: &[Token], source: &[char]) -> (String, Vec<(Span, Vec<char>)>) {
+    let instructions = lower(tokens, source);
+
+    let mut output = String::new();
+    let mut last: Option<(&[char], TokenKind)> = None;
+    let mut last_was_opening_quote = false;
+    let mut quotes = QuoteParity::default();
+
+    let mut iter = instructions.into_iter().peekable();
+
+    while let Some(instruction) = iter.next() {
+        match instruction {
+            Instruction::Begin => {
+                quotes = QuoteParity::default();
+            }
+            Instruction::End => {}
+            Instruction::Text(content, kind) => {
+                output.extend(content.iter());
+
+                last_was_opening_quote = if kind.is_quote() {
+                    quotes.consume()
+                } else {
+                    false
+                };
+                last = Some((content, kind));
+            }
+            Instruction::Break | Instruction::SynthesizedBreak(_) => {
+                let next = iter.peek().and_then(|next| match next {
+                    Instruction::Text(content, kind) => Some((*content, *kind)),
+                    _ => None,
+                });
+                let next_is_opening_quote = next.is_some_and(|(_, k)| k.is_quote()) && quotes.next_is_opening();
+
+                if break_wants_space(last, last_was_opening_quote, next, next_is_opening_quote) {
+                    output.push(' ');
+                }
+            }
+        }
+    }
+
+    let edits = build_edits(tokens, source);
+
+    (output, edits)
+}
+
+/// Diff the original whitespace tokens against the normalized run of
+/// replacement characters they should become, recorded as `(Span,
+/// replacement)` pairs.
+fn build_edits(tokens: &[Token], source: &[char]) -> Vec<(Span, Vec<char>)> {
+    let instructions = lower(tokens, source);
+    let mut spaces = tokens.iter().filter(|t| t.kind.is_space());
+
+    let mut edits = Vec::new();
+    let mut last: Option<(&[char], TokenKind)> = None;
+    let mut last_was_opening_quote = false;
+    let mut quotes = QuoteParity::default();
+
+    let mut iter = instructions.iter().peekable();
+
+    while let Some(instruction) = iter.next() {
+        match instruction {
+            Instruction::Begin => {
+                quotes = QuoteParity::default();
+            }
+            Instruction::End => {}
+            Instruction::Text(content, kind) => {
+                last_was_opening_quote = if kind.is_quote() {
+                    quotes.consume()
+                } else {
+                    false
+                };
+                last = Some((content, *kind));
+            }
+            Instruction::Break => {
+                let Some(space_token) = spaces.next() else {
+                    continue;
+                };
+
+                let next = iter.peek().and_then(|next| match next {
+                    Instruction::Text(content, kind) => Some((*content, *kind)),
+                    _ => None,
+                });
+                let next_is_opening_quote = next.is_some_and(|(_, k)| k.is_quote()) && quotes.next_is_opening();
+
+                let wants_space = break_wants_space(last, last_was_opening_quote, next, next_is_opening_quote);
+                let original = space_token.span.get_content(source);
+                let normalized: &[char] = if wants_space { &[' '] } else { &[] };
+
+                if original != normalized {
+                    edits.push((space_token.span, normalized.to_vec()));
+                }
+            }
+            Instruction::SynthesizedBreak(offset) => {
+                let next = iter.peek().and_then(|next| match next {
+                    Instruction::Text(content, kind) => Some((*content, *kind)),
+                    _ => None,
+                });
+                let next_is_opening_quote = next.is_some_and(|(_, k)| k.is_quote()) && quotes.next_is_opening();
+
+                // There's no whitespace token here to diff against -- the
+                // only possible edit is inserting the space this gap is
+                // missing.
+                if break_wants_space(last, last_was_opening_quote, next, next_is_opening_quote) {
+                    edits.push((Span::new(*offset, *offset), vec![' ']));
+                }
+            }
+        }
+    }
+
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_spacing;
+    use crate::parsers::{Parser, PlainEnglish};
+
+    #[test]
+    fn collapses_runs_of_spaces() {
+        let text = "Hello    world.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let (normalized, _) = normalize_spacing(&toks, &chars);
+        assert_eq!(normalized, "Hello world.");
+    }
+
+    #[test]
+    fn removes_space_before_terminal_punctuation() {
+        let text = "Hello world .";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let (normalized, _) = normalize_spacing(&toks, &chars);
+        assert_eq!(normalized, "Hello world.");
+    }
+
+    #[test]
+    fn removes_space_before_ellipsis() {
+        let text = "Wait ... really?";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let (normalized, _) = normalize_spacing(&toks, &chars);
+        assert_eq!(normalized, "Wait... really?");
+    }
+
+    #[test]
+    fn removes_space_just_inside_a_quote_pair() {
+        let text = "She said \" hello there \" and left.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let (normalized, _) = normalize_spacing(&toks, &chars);
+        assert_eq!(normalized, "She said \"hello there\" and left.");
+    }
+
+    #[test]
+    fn synthesizes_a_space_between_sentences_with_no_gap_at_all() {
+        let text = "Hi.Bye.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let (normalized, edits) = normalize_spacing(&toks, &chars);
+        assert_eq!(normalized, "Hi. Bye.");
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].0.start, edits[0].0.end);
+        assert_eq!(edits[0].1, vec![' ']);
+    }
+
+    #[test]
+    fn quote_parity_resets_per_sentence() {
+        // A stray, unpaired quote in the first sentence must not flip
+        // opening/closing detection for the quote pair in the second.
+        let text = "He said \". She said \"hi there\" back.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let (normalized, _) = normalize_spacing(&toks, &chars);
+        assert_eq!(normalized, "He said \". She said \"hi there\" back.");
+    }
+}
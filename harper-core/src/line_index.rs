@@ -0,0 +1,111 @@
+use crate::Span;
+
+/// A zero-indexed line/column location within a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Position {
+    pub fn new(line: u32, column: u32) -> Self {
+        Self { line, column }
+    }
+}
+
+/// An index of every newline in a document, allowing cheap conversion
+/// between char offsets and `(line, column)` positions for editor-facing
+/// tooling (e.g. LSP).
+///
+/// Built once per document with a single `O(n)` pass over the source;
+/// lookups are binary searches over the recorded newline offsets.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// Char offset of every `\n` in the source, in ascending order.
+    newlines: Vec<usize>,
+    source_len: usize,
+}
+
+impl LineIndex {
+    pub fn new(source: &[char]) -> Self {
+        let newlines = source
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == '\n')
+            .map(|(i, _)| i)
+            .collect();
+
+        Self {
+            newlines,
+            source_len: source.len(),
+        }
+    }
+
+    /// Convert a char offset into a `(line, column)` [`Position`].
+    pub fn position_of(&self, offset: usize) -> Position {
+        let line = self.newlines.partition_point(|&nl| nl < offset);
+        let line_start = if line == 0 {
+            0
+        } else {
+            self.newlines[line - 1] + 1
+        };
+
+        Position::new(line as u32, (offset - line_start) as u32)
+    }
+
+    /// Convert a `(line, column)` [`Position`] back into a char offset.
+    pub fn offset_of(&self, pos: Position) -> usize {
+        let line_start = if pos.line == 0 {
+            0
+        } else {
+            self.newlines
+                .get(pos.line as usize - 1)
+                .map(|nl| nl + 1)
+                .unwrap_or(self.source_len)
+        };
+
+        (line_start + pos.column as usize).min(self.source_len)
+    }
+}
+
+impl Span {
+    /// Convert this span's start and end offsets into editor-facing
+    /// `(line, column)` positions.
+    pub fn to_line_col(&self, index: &LineIndex) -> (Position, Position) {
+        (index.position_of(self.start), index.position_of(self.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineIndex, Position};
+    use crate::Span;
+
+    #[test]
+    fn round_trips_offsets_through_positions() {
+        let text = "first\nsecond\nthird";
+        let chars: Vec<char> = text.chars().collect();
+        let index = LineIndex::new(&chars);
+
+        assert_eq!(index.position_of(0), Position::new(0, 0));
+        assert_eq!(index.position_of(6), Position::new(1, 0));
+        assert_eq!(index.position_of(chars.len()), Position::new(2, 5));
+
+        for offset in 0..=chars.len() {
+            let pos = index.position_of(offset);
+            assert_eq!(index.offset_of(pos), offset);
+        }
+    }
+
+    #[test]
+    fn handles_span_ending_on_a_line_break() {
+        let text = "abc\ndef";
+        let chars: Vec<char> = text.chars().collect();
+        let index = LineIndex::new(&chars);
+
+        let span = Span::new(0, 3);
+        let (start, end) = span.to_line_col(&index);
+        assert_eq!(start, Position::new(0, 0));
+        assert_eq!(end, Position::new(0, 3));
+    }
+}
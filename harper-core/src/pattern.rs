@@ -0,0 +1,278 @@
+use crate::{Span, Token, TokenKind};
+
+/// A named capture produced by a successful [`Matcher`] run: the slice of
+/// the match this capture covers, expressed as an index range into the
+/// token stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    pub name: &'static str,
+    pub range: (usize, usize),
+}
+
+/// The result of successfully matching a [`Matcher`] against a token
+/// stream starting at some index: how many tokens were consumed, and any
+/// named captures collected along the way.
+#[derive(Debug, Clone, Default)]
+pub struct MatchOutcome {
+    pub len: usize,
+    pub captures: Vec<Capture>,
+}
+
+impl MatchOutcome {
+    fn token(len: usize) -> Self {
+        Self {
+            len,
+            captures: Vec::new(),
+        }
+    }
+
+    fn extend(mut self, other: MatchOutcome) -> Self {
+        self.len += other.len;
+        self.captures.extend(other.captures);
+        self
+    }
+}
+
+/// A declarative matcher over `&[Token]`, composed from small primitives
+/// instead of imperative span arithmetic.
+///
+/// Every combinator skips optional intervening whitespace by default, so
+/// `kind(TokenKind::Word(..)).then(linking_verb())` matches "word, then a
+/// linking verb" regardless of the spaces between them.
+pub trait Matcher {
+    /// Attempt to match starting at `tokens[start..]`. On success, returns
+    /// how many tokens (including skipped whitespace) were consumed.
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome>;
+
+    /// Run this matcher against every position in `tokens`, yielding the
+    /// matched sub-slice's [`Span`] for each match found.
+    ///
+    /// Only non-whitespace positions are tried as a match start: every
+    /// primitive matcher skips *leading* whitespace internally (so a match
+    /// can be found starting right after a space), and since that skip is
+    /// folded into `MatchOutcome::len`, starting a scan on the whitespace
+    /// token itself would both re-discover the same match with a
+    /// leading-space-inclusive (and therefore wrong) `Span`, and report it
+    /// twice -- once from the space, once from the token after it.
+    fn find_iter<'a>(&'a self, tokens: &'a [Token]) -> impl Iterator<Item = Span> + 'a
+    where
+        Self: Sized,
+    {
+        (0..tokens.len())
+            .filter(|&start| !tokens[start].kind.is_space())
+            .filter_map(move |start| {
+                let outcome = self.try_match(tokens, start)?;
+                if outcome.len == 0 {
+                    return None;
+                }
+
+                let first = tokens[start];
+                let last = tokens[start + outcome.len - 1];
+                Some(Span::new(first.span.start, last.span.end))
+            })
+    }
+
+    fn then<B: Matcher>(self, next: B) -> Then<Self, B>
+    where
+        Self: Sized,
+    {
+        Then { first: self, second: next }
+    }
+
+    fn or<B: Matcher>(self, alt: B) -> Or<Self, B>
+    where
+        Self: Sized,
+    {
+        Or { first: self, second: alt }
+    }
+
+    fn optional(self) -> Optional<Self>
+    where
+        Self: Sized,
+    {
+        Optional { inner: self }
+    }
+
+    fn repeat(self, min: usize) -> Repeat<Self>
+    where
+        Self: Sized,
+    {
+        Repeat { inner: self, min }
+    }
+
+    fn capture(self, name: &'static str) -> Captured<Self>
+    where
+        Self: Sized,
+    {
+        Captured { inner: self, name }
+    }
+}
+
+/// Skip over any run of whitespace tokens starting at `start`, returning
+/// the number of tokens skipped.
+fn skip_whitespace(tokens: &[Token], start: usize) -> usize {
+    tokens[start..]
+        .iter()
+        .take_while(|t| t.kind.is_space())
+        .count()
+}
+
+/// Match a single token of an exact [`TokenKind`].
+pub fn kind(kind: TokenKind) -> Kind {
+    Kind { kind }
+}
+
+pub struct Kind {
+    kind: TokenKind,
+}
+
+impl Matcher for Kind {
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome> {
+        let skipped = skip_whitespace(tokens, start);
+        let tok = tokens.get(start + skipped)?;
+        (tok.kind == self.kind).then(|| MatchOutcome::token(skipped + 1))
+    }
+}
+
+/// Match a single token satisfying an arbitrary [`TokenKind`] predicate,
+/// e.g. `predicate(TokenKind::is_conjunction)`. This is how the existing
+/// `TokenKind` classifiers (`is_conjunction`, `is_likely_homograph`, etc.)
+/// plug in as leaf matchers.
+pub fn predicate<F>(f: F) -> Predicate<F>
+where
+    F: Fn(&TokenKind) -> bool,
+{
+    Predicate { f }
+}
+
+pub struct Predicate<F> {
+    f: F,
+}
+
+impl<F> Matcher for Predicate<F>
+where
+    F: Fn(&TokenKind) -> bool,
+{
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome> {
+        let skipped = skip_whitespace(tokens, start);
+        let tok = tokens.get(start + skipped)?;
+        (self.f)(&tok.kind).then(|| MatchOutcome::token(skipped + 1))
+    }
+}
+
+pub fn word_like() -> Predicate<fn(&TokenKind) -> bool> {
+    predicate(TokenKind::is_word_like)
+}
+
+pub fn linking_verb() -> Predicate<fn(&TokenKind) -> bool> {
+    predicate(|k| matches!(k, TokenKind::Word(w) if w.is_linking_verb()))
+}
+
+pub struct Then<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Matcher, B: Matcher> Matcher for Then<A, B> {
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome> {
+        let first = self.first.try_match(tokens, start)?;
+        let second = self.second.try_match(tokens, start + first.len)?;
+        Some(first.extend(second))
+    }
+}
+
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Matcher, B: Matcher> Matcher for Or<A, B> {
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome> {
+        self.first
+            .try_match(tokens, start)
+            .or_else(|| self.second.try_match(tokens, start))
+    }
+}
+
+pub struct Optional<A> {
+    inner: A,
+}
+
+impl<A: Matcher> Matcher for Optional<A> {
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome> {
+        Some(self.inner.try_match(tokens, start).unwrap_or_default())
+    }
+}
+
+pub struct Repeat<A> {
+    inner: A,
+    min: usize,
+}
+
+impl<A: Matcher> Matcher for Repeat<A> {
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome> {
+        let mut outcome = MatchOutcome::default();
+        let mut count = 0;
+
+        loop {
+            match self.inner.try_match(tokens, start + outcome.len) {
+                Some(next) if next.len > 0 => {
+                    outcome = outcome.extend(next);
+                    count += 1;
+                }
+                _ => break,
+            }
+        }
+
+        (count >= self.min).then_some(outcome)
+    }
+}
+
+pub struct Captured<A> {
+    inner: A,
+    name: &'static str,
+}
+
+impl<A: Matcher> Matcher for Captured<A> {
+    fn try_match(&self, tokens: &[Token], start: usize) -> Option<MatchOutcome> {
+        let skipped = skip_whitespace(tokens, start);
+        let mut outcome = self.inner.try_match(tokens, start)?;
+
+        outcome.captures.push(Capture {
+            name: self.name,
+            range: (start + skipped, start + outcome.len),
+        });
+
+        Some(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kind, linking_verb, word_like, Matcher};
+    use crate::parsers::{Parser, PlainEnglish};
+    use crate::TokenKind;
+
+    #[test]
+    fn matches_linking_verb_then_word() {
+        let text = "The cat is happy.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let matcher = linking_verb().then(word_like().capture("predicate"));
+        let matches: Vec<_> = matcher.find_iter(&toks).collect();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].get_content_string(&chars), "is happy");
+    }
+
+    #[test]
+    fn or_falls_back_to_second_branch() {
+        let text = "cats";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let matcher = kind(TokenKind::Number(Default::default())).or(word_like());
+        assert_eq!(matcher.find_iter(&toks).count(), 1);
+    }
+}
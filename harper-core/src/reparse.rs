@@ -0,0 +1,144 @@
+use crate::parsers::{Parser, PlainEnglish};
+use crate::{Span, Token, TokenStringExt};
+
+/// A single replacement applied to a source buffer: the [`Span`] of
+/// characters in the *old* source being removed, and the characters put in
+/// their place.
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub span: Span,
+    pub replacement: Vec<char>,
+}
+
+impl Edit {
+    pub fn new(span: Span, replacement: Vec<char>) -> Self {
+        Self { span, replacement }
+    }
+
+    /// The change in character count this edit introduces.
+    fn delta(&self) -> isize {
+        self.replacement.len() as isize - (self.span.end - self.span.start) as isize
+    }
+}
+
+/// Re-tokenize only the region of `new_src` affected by `edit`, splicing the
+/// result back into a copy of `old`.
+///
+/// The result is byte-identical to calling [`PlainEnglish::parse`] on
+/// `new_src` from scratch, but only the window around the edit is re-lexed:
+/// tokens before it are copied unchanged and tokens after it keep their
+/// kinds, with spans shifted by the edit's change in length. The returned
+/// `Vec` still holds the whole token stream, so allocation grows with the
+/// document, not just with the window.
+pub fn reparse(old: &[Token], old_src: &[char], edit: &Edit, new_src: &[char]) -> Vec<Token> {
+    let delta = edit.delta();
+
+    let (window_start, window_end) = expand_to_boundaries(old, old_src, edit.span);
+
+    let before = old
+        .iter()
+        .take_while(|tok| tok.span.end <= window_start)
+        .copied()
+        .collect::<Vec<_>>();
+
+    let after = old
+        .iter()
+        .skip_while(|tok| tok.span.end <= window_start)
+        .skip_while(|tok| tok.span.start < window_end)
+        .map(|tok| {
+            let mut shifted = *tok;
+            shifted.span = Span::new(
+                (tok.span.start as isize + delta) as usize,
+                (tok.span.end as isize + delta) as usize,
+            );
+            shifted
+        })
+        .collect::<Vec<_>>();
+
+    let new_window_end = (window_end as isize + delta) as usize;
+    let window_src = &new_src[window_start..new_window_end];
+
+    let reparsed = PlainEnglish
+        .parse(window_src)
+        .into_iter()
+        .map(|mut tok| {
+            tok.span = Span::new(tok.span.start + window_start, tok.span.end + window_start);
+            tok
+        })
+        .collect::<Vec<_>>();
+
+    before
+        .into_iter()
+        .chain(reparsed)
+        .chain(after)
+        .collect()
+}
+
+/// Expand `span` outward to the nearest chunk terminator or whitespace in
+/// `old` on each side, so a word split by the edit is fully re-lexed rather
+/// than left half-stale.
+///
+/// A bordering *whitespace* token is pulled into the window rather than
+/// left untouched: if the edit removes the word between two separate runs
+/// of whitespace, those runs become textually adjacent and a fresh parse
+/// would lex them as a single, longer `Space` token. Leaving either run
+/// outside the window would instead leave two stale, adjacent `Space`
+/// tokens where a full reparse produces one. A bordering chunk terminator
+/// can't merge with anything this way, so it's left outside the window as
+/// before.
+fn expand_to_boundaries(old: &[Token], _old_src: &[char], span: Span) -> (usize, usize) {
+    let start = old
+        .iter()
+        .rev()
+        .filter(|tok| tok.span.end <= span.start)
+        .find(|tok| tok.kind.is_chunk_terminator() || tok.kind.is_space())
+        .map(|tok| if tok.kind.is_space() { tok.span.start } else { tok.span.end })
+        .unwrap_or(0);
+
+    let end = old
+        .iter()
+        .filter(|tok| tok.span.start >= span.end)
+        .find(|tok| tok.kind.is_chunk_terminator() || tok.kind.is_space())
+        .map(|tok| if tok.kind.is_space() { tok.span.end } else { tok.span.start })
+        .unwrap_or_else(|| old.span().map(|s| s.end).unwrap_or(span.end));
+
+    (start, end.max(span.end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reparse, Edit};
+    use crate::parsers::{Parser, PlainEnglish};
+    use crate::Span;
+
+    /// `reparse` should always produce the same token stream a full
+    /// `PlainEnglish::parse` of `new_text` would.
+    fn assert_matches_full_reparse(old_text: &str, span: Span, replacement: &str, new_text: &str) {
+        let old_src: Vec<char> = old_text.chars().collect();
+        let new_src: Vec<char> = new_text.chars().collect();
+        let old_toks = PlainEnglish.parse(&old_src);
+
+        let edit = Edit::new(span, replacement.chars().collect());
+        let incremental = reparse(&old_toks, &old_src, &edit, &new_src);
+        let full = PlainEnglish.parse(&new_src);
+
+        assert_eq!(incremental, full);
+    }
+
+    #[test]
+    fn merges_whitespace_left_adjacent_by_a_deletion() {
+        // Deleting "bb" leaves the two single-space runs on either side of
+        // it textually adjacent; a full reparse lexes them as one `Space`.
+        assert_matches_full_reparse("a bb c", Span::new(2, 4), "", "a  c");
+    }
+
+    #[test]
+    fn replaces_a_single_word_mid_sentence() {
+        assert_matches_full_reparse(
+            "I have alot of cats.",
+            Span::new(7, 11),
+            "a lot",
+            "I have a lot of cats.",
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::rc::Rc;
+
+use crate::{Span, Token, TokenKind, TokenStringExt};
+
+/// The kind of a node in a [`SyntaxNode`] tree.
+///
+/// Leaf nodes map directly onto the [`TokenKind`] of the [`Token`] they were
+/// built from; the remaining variants are synthetic, introduced purely to
+/// describe the document structure that sits above individual tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyntaxKind {
+    Document,
+    Paragraph,
+    Sentence,
+    Chunk,
+    Token(TokenKind),
+}
+
+/// An immutable, offset-free tree node, following the "green tree" half of
+/// the green/red design used by rust-analyzer.
+///
+/// A green node never stores an absolute position -- only its own kind, its
+/// children, and its relative text length (note that a token leaf stores no
+/// text either, only its length: two tokens of the same kind and length are
+/// indistinguishable at this layer). Because of that, two subtrees built
+/// from the same shape -- same kind, same length, same child sequence -- are
+/// hash-consed by [`GreenCache`] into the very same, reference-counted
+/// `GreenNode`. The cache lives for a single
+/// [`to_tree`](SyntaxTreeExt::to_tree) call, so sharing happens within one
+/// tree only: a later `to_tree` call builds every node afresh and shares
+/// nothing with trees built before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GreenNode {
+    kind: SyntaxKind,
+    len: usize,
+    children: Vec<Rc<GreenNode>>,
+}
+
+impl GreenNode {
+    pub fn kind(&self) -> SyntaxKind {
+        self.kind
+    }
+
+    /// The length, in characters, of the text this node spans.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn children(&self) -> &[Rc<GreenNode>] {
+        &self.children
+    }
+}
+
+/// A hash-consing cache used while building a green tree: every node it
+/// hands out is structurally unique, so two calls asking for "a chunk made
+/// of these exact child nodes" get back the same `Rc`, regardless of where
+/// in the document those children came from.
+///
+/// Children are already canonicalized by the time a branch is interned, so
+/// comparing them by pointer (rather than deeply, by value) is enough to
+/// detect a repeated shape.
+#[derive(Debug, Default)]
+struct GreenCache {
+    tokens: Vec<Rc<GreenNode>>,
+    branches: Vec<Rc<GreenNode>>,
+}
+
+impl GreenCache {
+    fn token(&mut self, kind: TokenKind, len: usize) -> Rc<GreenNode> {
+        let kind = SyntaxKind::Token(kind);
+
+        if let Some(existing) = self.tokens.iter().find(|n| n.kind == kind && n.len == len) {
+            return existing.clone();
+        }
+
+        let node = Rc::new(GreenNode {
+            kind,
+            len,
+            children: Vec::new(),
+        });
+        self.tokens.push(node.clone());
+        node
+    }
+
+    fn branch(&mut self, kind: SyntaxKind, children: Vec<Rc<GreenNode>>) -> Rc<GreenNode> {
+        if let Some(existing) = self.branches.iter().find(|n| {
+            n.kind == kind
+                && n.children.len() == children.len()
+                && n.children.iter().zip(&children).all(|(a, b)| Rc::ptr_eq(a, b))
+        }) {
+            return existing.clone();
+        }
+
+        let len = children.iter().map(|child| child.len).sum();
+        let node = Rc::new(GreenNode { kind, len, children });
+        self.branches.push(node.clone());
+        node
+    }
+}
+
+/// A lazily-materialized "red" view over a [`GreenNode`].
+///
+/// The red layer carries exactly what the green tree intentionally omits: a
+/// parent pointer and an absolute [`Span`], computed on the fly as the
+/// offset accumulated from preceding siblings. Red nodes are cheap to build
+/// while traversing and are never stored back onto the green tree.
+#[derive(Debug, Clone)]
+pub struct SyntaxNode {
+    green: Rc<GreenNode>,
+    parent: Option<Rc<SyntaxNode>>,
+    offset: usize,
+}
+
+impl SyntaxNode {
+    fn new_root(green: Rc<GreenNode>) -> Self {
+        Self {
+            green,
+            parent: None,
+            offset: 0,
+        }
+    }
+
+    pub fn kind(&self) -> SyntaxKind {
+        self.green.kind()
+    }
+
+    /// The absolute span of this node within the original document.
+    pub fn span(&self) -> Span {
+        Span::new(self.offset, self.offset + self.green.len())
+    }
+
+    pub fn parent(&self) -> Option<&SyntaxNode> {
+        self.parent.as_deref()
+    }
+
+    /// Materialize the red layer for each child, accumulating the absolute
+    /// offset from preceding siblings as we go.
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        let parent = Rc::new(self.clone());
+        let mut offset = self.offset;
+
+        self.green.children().iter().map(move |green_child| {
+            let child = SyntaxNode {
+                green: green_child.clone(),
+                parent: Some(parent.clone()),
+                offset,
+            };
+            offset += green_child.len();
+            child
+        })
+    }
+
+    pub fn next_sibling(&self) -> Option<SyntaxNode> {
+        let parent = self.parent()?;
+
+        parent
+            .children()
+            .skip_while(|child| child.offset != self.offset || !Rc::ptr_eq(&child.green, &self.green))
+            .nth(1)
+    }
+}
+
+/// Build a lossless hierarchical tree (Document -> Paragraph -> Sentence ->
+/// Chunk -> Token) over a flat token stream.
+pub trait SyntaxTreeExt {
+    fn to_tree(&self) -> SyntaxNode;
+}
+
+impl SyntaxTreeExt for [Token] {
+    fn to_tree(&self) -> SyntaxNode {
+        let mut cache = GreenCache::default();
+
+        let paragraphs = self
+            .iter_paragraphs()
+            .map(|paragraph| paragraph_to_green(paragraph, &mut cache))
+            .collect();
+
+        SyntaxNode::new_root(cache.branch(SyntaxKind::Document, paragraphs))
+    }
+}
+
+fn paragraph_to_green(paragraph: &[Token], cache: &mut GreenCache) -> Rc<GreenNode> {
+    let sentences = paragraph
+        .iter_sentences()
+        .map(|sentence| sentence_to_green(sentence, cache))
+        .collect();
+
+    cache.branch(SyntaxKind::Paragraph, sentences)
+}
+
+fn sentence_to_green(sentence: &[Token], cache: &mut GreenCache) -> Rc<GreenNode> {
+    let chunks = sentence
+        .iter_chunks()
+        .map(|chunk| chunk_to_green(chunk, cache))
+        .collect();
+
+    cache.branch(SyntaxKind::Sentence, chunks)
+}
+
+fn chunk_to_green(chunk: &[Token], cache: &mut GreenCache) -> Rc<GreenNode> {
+    let tokens = chunk
+        .iter()
+        .map(|tok| cache.token(tok.kind, tok.span.end - tok.span.start))
+        .collect();
+
+    cache.branch(SyntaxKind::Chunk, tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{SyntaxKind, SyntaxTreeExt};
+    use crate::parsers::{Parser, PlainEnglish};
+
+    #[test]
+    fn builds_document_paragraph_sentence_chunk_token_tree() {
+        let text = "There were three little pigs. They built three little homes.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let doc = toks.to_tree();
+        assert_eq!(doc.kind(), SyntaxKind::Document);
+        assert_eq!(doc.span().get_content_string(&chars), text);
+
+        let paragraph = doc.children().next().unwrap();
+        assert_eq!(paragraph.kind(), SyntaxKind::Paragraph);
+
+        let sentences: Vec<_> = paragraph.children().collect();
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(
+            sentences[0].span().get_content_string(&chars),
+            "There were three little pigs."
+        );
+
+        let first_chunk = sentences[0].children().next().unwrap();
+        assert_eq!(first_chunk.kind(), SyntaxKind::Chunk);
+        assert_eq!(first_chunk.parent().unwrap().span(), sentences[0].span());
+
+        let second_sentence = sentences[0].next_sibling().unwrap();
+        assert_eq!(second_sentence.span(), sentences[1].span());
+    }
+
+    #[test]
+    fn shares_identically_shaped_subtrees_by_pointer() {
+        // The second and third sentences are both " a a." -- chunks with the
+        // exact same shape (space, word, space, word, period) -- so the
+        // cache should hand back the same green node. The first sentence
+        // has no leading space and so a different shape.
+        let text = "a a. a a. a a.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let doc = toks.to_tree();
+        let sentences: Vec<_> = doc.children().next().unwrap().children().collect();
+        assert_eq!(sentences.len(), 3);
+
+        let first_chunk = sentences[1].children().next().unwrap();
+        let second_chunk = sentences[2].children().next().unwrap();
+
+        assert!(Rc::ptr_eq(&first_chunk.green, &second_chunk.green));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{FatToken, Span, Token};
+
+/// A single batched change, expressed as a half-open range over the
+/// *original* token stream.
+#[derive(Debug, Clone)]
+enum Change {
+    Insert { at: usize, tokens: Vec<FatToken> },
+    Replace { range: (usize, usize), tokens: Vec<FatToken> },
+    Delete { range: (usize, usize) },
+}
+
+impl Change {
+    /// The half-open range over the original stream this change touches.
+    /// An insertion is a zero-width range at its insertion point.
+    fn range(&self) -> (usize, usize) {
+        match self {
+            Change::Insert { at, .. } => (*at, *at),
+            Change::Replace { range, .. } => *range,
+            Change::Delete { range } => *range,
+        }
+    }
+}
+
+/// A batch of structural edits over a token stream, modeled on
+/// rust-analyzer's `AstEditor`.
+///
+/// Callers describe edits (`insert`, `replace`, `delete`) in terms of
+/// indices into the *original* stream -- no index math is required between
+/// calls, since edits are only resolved and applied once, on [`finish`].
+///
+/// [`finish`]: TokenEditor::finish
+pub struct TokenEditor<'a> {
+    tokens: &'a [Token],
+    source: &'a [char],
+    changes: Vec<Change>,
+}
+
+impl<'a> TokenEditor<'a> {
+    pub fn new(tokens: &'a [Token], source: &'a [char]) -> Self {
+        Self {
+            tokens,
+            source,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, tokens: Vec<FatToken>) {
+        self.changes.push(Change::Insert { at: index, tokens });
+    }
+
+    pub fn replace(&mut self, range: (usize, usize), tokens: Vec<FatToken>) {
+        self.changes.push(Change::Replace { range, tokens });
+    }
+
+    pub fn delete(&mut self, range: (usize, usize)) {
+        self.changes.push(Change::Delete { range });
+    }
+
+    /// Apply all batched edits, rebuilding the token stream in one pass.
+    ///
+    /// Edits are resolved back-to-front so that earlier edits still
+    /// reference indices into the original stream, then recomputes every
+    /// span in a single forward pass. Returns the new token stream along
+    /// with the diff against the original source, as `(Span, Vec<char>)`
+    /// pairs. The edited source text itself is not returned: the new spans
+    /// index the concatenated contents of the resulting tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two batched changes overlap (an insertion landing exactly
+    /// on another change's boundary is fine and resolved in document order;
+    /// a range genuinely shared by two changes is a caller bug, since there
+    /// is no sound way to decide which one "wins").
+    pub fn finish(mut self) -> (Vec<Token>, Vec<(Span, Vec<char>)>) {
+        // Back-to-front so that earlier changes don't need their indices
+        // adjusted as later (and, in document order, earlier) ones apply.
+        // Ties (same start) are broken by descending end, so a change that
+        // spans a range is applied before an insertion landing at its
+        // start -- e.g. `insert(2, ..)` alongside `replace((2, 4), ..)`
+        // resolves to "insert before the replacement", not index corruption.
+        self.changes
+            .sort_by(|a, b| a.range().cmp(&b.range()).reverse());
+
+        assert_no_overlaps(&self.changes);
+
+        let mut fat_tokens: Vec<FatToken> = self.tokens.iter().map(|t| t.to_fat(self.source)).collect();
+        let mut diffs = Vec::new();
+
+        for change in self.changes {
+            match change {
+                Change::Insert { at, tokens } => {
+                    let point = self.insertion_point(at);
+                    let replacement: Vec<char> = tokens.iter().flat_map(|t| t.content.clone()).collect();
+                    diffs.push((Span::new(point, point), replacement));
+
+                    splice(&mut fat_tokens, at, at, tokens);
+                }
+                Change::Replace { range, tokens } => {
+                    if let Some(span) = self.span_of(range) {
+                        let replacement: Vec<char> = tokens.iter().flat_map(|t| t.content.clone()).collect();
+                        diffs.push((span, replacement));
+                    }
+
+                    splice(&mut fat_tokens, range.0, range.1, tokens);
+                }
+                Change::Delete { range } => {
+                    if let Some(span) = self.span_of(range) {
+                        diffs.push((span, Vec::new()));
+                    }
+
+                    splice(&mut fat_tokens, range.0, range.1, Vec::new());
+                }
+            }
+        }
+
+        diffs.reverse();
+
+        let new_tokens = rebuild_spans(&fat_tokens);
+
+        (new_tokens, diffs)
+    }
+
+    /// The char offset an insertion at token index `at` lands on: the start
+    /// of the token that used to sit there, or the end of the source if
+    /// `at` is at or past the end of the stream (appending at EOF, or
+    /// inserting into an empty stream).
+    fn insertion_point(&self, at: usize) -> usize {
+        self.tokens
+            .get(at)
+            .map(|t| t.span.start)
+            .unwrap_or(self.source.len())
+    }
+
+    fn span_of(&self, range: (usize, usize)) -> Option<Span> {
+        if range.0 == range.1 {
+            // A zero-width range (e.g. a zero-width capture fed into
+            // `replace`/`delete`) is just an insertion point.
+            let at = self.tokens.get(range.0)?.span.start;
+            return Some(Span::new(at, at));
+        }
+
+        let start = self.tokens.get(range.0)?.span.start;
+        let end = self.tokens.get(range.1 - 1)?.span.end;
+        Some(Span::new(start, end))
+    }
+}
+
+/// Two changes conflict if their original-stream ranges genuinely overlap
+/// rather than merely touch at a boundary -- touching is resolved by the
+/// sort order in [`TokenEditor::finish`].
+///
+/// `changes` must be sorted by descending range. Every change seen before
+/// the current one then starts at or after it, and the most recent
+/// non-empty one has the lowest start, so it is the only one that can reach
+/// back into the current change -- even with insertions sorted in between.
+fn assert_no_overlaps(changes: &[Change]) {
+    let mut lowest_non_empty: Option<(usize, usize)> = None;
+
+    for change in changes {
+        let (b_start, b_end) = change.range();
+
+        if let Some((a_start, a_end)) = lowest_non_empty {
+            if a_start.max(b_start) < a_end.min(b_end) {
+                panic!(
+                    "TokenEditor: overlapping changes over original range ({a_start}, {a_end}) and ({b_start}, {b_end})"
+                );
+            }
+        }
+
+        if b_start < b_end {
+            lowest_non_empty = Some((b_start, b_end));
+        }
+    }
+}
+
+fn splice(fat_tokens: &mut Vec<FatToken>, start: usize, end: usize, replacement: Vec<FatToken>) {
+    fat_tokens.splice(start..end, replacement);
+}
+
+/// Recompute every span from scratch given the final, ordered content.
+fn rebuild_spans(fat_tokens: &[FatToken]) -> Vec<Token> {
+    let mut offset = 0;
+    let mut tokens = Vec::with_capacity(fat_tokens.len());
+
+    for fat in fat_tokens {
+        let span = Span::new(offset, offset + fat.content.len());
+        offset = span.end;
+        tokens.push(Token::new(span, fat.kind));
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenEditor;
+    use crate::parsers::{Parser, PlainEnglish};
+
+    #[test]
+    fn replace_recomputes_trailing_spans() {
+        let text = "I have alot of cats.";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let replacement_src: Vec<char> = "a lot".chars().collect();
+        let replacement_tokens: Vec<_> = PlainEnglish
+            .parse(&replacement_src)
+            .into_iter()
+            .map(|t| t.to_fat(&replacement_src))
+            .collect();
+
+        let alot_idx = toks
+            .iter()
+            .position(|t| t.span.get_content(&chars).iter().collect::<String>() == "alot")
+            .unwrap();
+
+        let mut editor = TokenEditor::new(&toks, &chars);
+        editor.replace((alot_idx, alot_idx + 1), replacement_tokens);
+
+        let (new_tokens, diffs) = editor.finish();
+
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(new_tokens.first().unwrap().span.start, 0);
+        assert!(new_tokens
+            .windows(2)
+            .all(|w| w[0].span.end == w[1].span.start));
+    }
+
+    #[test]
+    fn insert_at_a_replace_range_start_lands_before_the_replacement() {
+        let text = "a b c d";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let inserted_src: Vec<char> = "X".chars().collect();
+        let inserted = vec![PlainEnglish.parse(&inserted_src)[0].to_fat(&inserted_src)];
+
+        let replaced_src: Vec<char> = "Y".chars().collect();
+        let replaced = vec![PlainEnglish.parse(&replaced_src)[0].to_fat(&replaced_src)];
+
+        // `b` is token index 2 (after the space following `a`); replace it
+        // with `Y` while also inserting `X` right before it.
+        let b_idx = toks
+            .iter()
+            .position(|t| t.span.get_content(&chars).iter().collect::<String>() == "b")
+            .unwrap();
+
+        let mut editor = TokenEditor::new(&toks, &chars);
+        editor.insert(b_idx, inserted);
+        editor.replace((b_idx, b_idx + 1), replaced);
+
+        // Must not panic (the two changes only touch at a boundary) and
+        // must keep producing a contiguous token stream.
+        let (new_tokens, diffs) = editor.finish();
+        assert_eq!(diffs.len(), 2);
+        assert!(new_tokens.windows(2).all(|w| w[0].span.end == w[1].span.start));
+    }
+
+    #[test]
+    #[should_panic(expected = "overlapping changes")]
+    fn rejects_genuinely_overlapping_changes() {
+        let text = "a b c d";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let mut editor = TokenEditor::new(&toks, &chars);
+        editor.replace((0, 3), Vec::new());
+        editor.replace((1, 4), Vec::new());
+        editor.finish();
+    }
+
+    #[test]
+    fn delete_of_a_zero_width_range_is_a_no_op_insertion_point() {
+        let text = "a b c";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let mut editor = TokenEditor::new(&toks, &chars);
+        editor.delete((1, 1));
+
+        let (new_tokens, diffs) = editor.finish();
+        assert_eq!(new_tokens.len(), toks.len());
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].0.start, diffs[0].0.end);
+    }
+
+    #[test]
+    fn insert_at_end_of_stream_still_produces_a_diff() {
+        let text = "a b c";
+        let chars: Vec<char> = text.chars().collect();
+        let toks = PlainEnglish.parse(&chars);
+
+        let inserted_src: Vec<char> = "d".chars().collect();
+        let inserted = vec![PlainEnglish.parse(&inserted_src)[0].to_fat(&inserted_src)];
+
+        let mut editor = TokenEditor::new(&toks, &chars);
+        editor.insert(toks.len(), inserted);
+
+        let (new_tokens, diffs) = editor.finish();
+        assert_eq!(new_tokens.len(), toks.len() + 1);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].0.start, chars.len());
+        assert_eq!(diffs[0].0.end, chars.len());
+        assert_eq!(diffs[0].1, vec!['d']);
+    }
+}